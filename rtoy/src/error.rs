@@ -0,0 +1,23 @@
+use std::fmt;
+
+/// An error produced while compiling a source input.
+///
+/// Lexing and parsing both surface through this type so the driver can treat
+/// any failure uniformly, whether it aborts a file run or just skips a REPL
+/// line.
+#[derive(Debug)]
+pub enum CompileError {
+    Io(String),
+    Scan(String),
+    Parse(String),
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompileError::Io(message) => write!(f, "io error: {message}"),
+            CompileError::Scan(message) => write!(f, "scan error: {message}"),
+            CompileError::Parse(message) => write!(f, "parse error: {message}"),
+        }
+    }
+}
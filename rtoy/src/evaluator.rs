@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use crate::parser::Instruction;
+use crate::parser::Literal;
+
+/// A runtime value produced while executing the instruction stream.
+#[derive(Debug, Clone)]
+pub enum Value {
+    Number(f64),
+    Nil,
+}
+
+/// Long-lived evaluator state that survives between REPL iterations.
+///
+/// Built-in operations are registered once in [`Environment::core_environment`]
+/// and reused, while `bindings` keeps variable definitions alive across lines.
+pub struct Environment {
+    bindings: HashMap<String, Value>,
+    builtins: HashMap<String, fn(Value, Value) -> Value>,
+}
+
+impl Environment {
+    /// Build the base environment with the built-in operations registered.
+    pub fn core_environment() -> Environment {
+        let mut builtins: HashMap<String, fn(Value, Value) -> Value> = HashMap::new();
+        builtins.insert("+".to_string(), add);
+        builtins.insert("-".to_string(), subtract);
+        builtins.insert("*".to_string(), multiply);
+        builtins.insert("/".to_string(), divide);
+
+        Environment {
+            bindings: HashMap::new(),
+            builtins,
+        }
+    }
+}
+
+/// Walk the instruction stream against a runtime value stack and return the
+/// computed result, resolving constants from `literals` and operations and
+/// variable bindings from `env`.
+pub fn eval(instructions: &[Instruction], literals: &[Literal], env: &mut Environment) -> Value {
+    let mut stack: Vec<Value> = Vec::new();
+
+    for instruction in instructions {
+        match instruction {
+            Instruction::Constant(index) => {
+                // A constant index the parser didn't back with a literal
+                // evaluates to nil rather than crashing the REPL.
+                let value = literals.get(*index).map(literal_value).unwrap_or(Value::Nil);
+                stack.push(value);
+            }
+            Instruction::GetVar(name) => {
+                let value = env.bindings.get(name).cloned().unwrap_or(Value::Nil);
+                stack.push(value);
+            }
+            Instruction::SetVar(name) => {
+                let value = stack.last().cloned().unwrap_or(Value::Nil);
+                env.bindings.insert(name.clone(), value);
+            }
+            Instruction::Op(name) => {
+                let rhs = stack.pop().unwrap_or(Value::Nil);
+                let lhs = stack.pop().unwrap_or(Value::Nil);
+                // An unknown operator evaluates to nil rather than crashing the REPL.
+                let value = match env.builtins.get(name) {
+                    Some(op) => op(lhs, rhs),
+                    None => Value::Nil,
+                };
+                stack.push(value);
+            }
+        }
+    }
+
+    stack.pop().unwrap_or(Value::Nil)
+}
+
+fn literal_value(literal: &Literal) -> Value {
+    match literal {
+        Literal::Number(n) => Value::Number(*n),
+    }
+}
+
+fn add(lhs: Value, rhs: Value) -> Value {
+    Value::Number(as_number(lhs) + as_number(rhs))
+}
+
+fn subtract(lhs: Value, rhs: Value) -> Value {
+    Value::Number(as_number(lhs) - as_number(rhs))
+}
+
+fn multiply(lhs: Value, rhs: Value) -> Value {
+    Value::Number(as_number(lhs) * as_number(rhs))
+}
+
+fn divide(lhs: Value, rhs: Value) -> Value {
+    Value::Number(as_number(lhs) / as_number(rhs))
+}
+
+fn as_number(value: Value) -> f64 {
+    match value {
+        Value::Number(n) => n,
+        Value::Nil => 0.0,
+    }
+}
@@ -0,0 +1,34 @@
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Cursor;
+use std::io::Seek;
+use std::io::SeekFrom;
+
+/// An input source the scanner can read from uniformly.
+///
+/// This lets `Scanner::scan` work the same way whether the source is an
+/// in-memory string (tests), an open file (file mode), or stdin (the REPL).
+pub enum Stream {
+    String(String),
+    File(File),
+    Stdin,
+}
+
+impl Stream {
+    /// Yield a buffered reader over the underlying source.
+    ///
+    /// For `File` the cursor is rewound to the start first so the stream can
+    /// be re-read from the beginning on each call.
+    pub fn reader(&mut self) -> io::Result<Box<dyn BufRead + '_>> {
+        match self {
+            Stream::String(s) => Ok(Box::new(Cursor::new(s.as_bytes()))),
+            Stream::File(f) => {
+                f.seek(SeekFrom::Start(0))?;
+                Ok(Box::new(BufReader::new(f)))
+            }
+            Stream::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+}